@@ -1,21 +1,378 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::{create_dir_all, OpenOptions};
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use tauri::{AppHandle, Manager};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Builds the environment for a backend, given the already-resolved base URLs (keyed by
+/// backend name) of every backend in the topology, so a dependent can read e.g.
+/// `resolved["karios-ai-service"]` instead of a literal.
+type EnvBuilder = fn(&AppHandle, u16, &HashMap<&'static str, String>) -> Vec<(&'static str, String)>;
+type ProbeBuilder = fn(u16) -> ReadinessProbe;
+
+/// Declarative description of one sidecar: where its binary lives, how to build its
+/// environment, how to confirm it's ready, and which other backends it depends on. Adding a
+/// third sidecar is a matter of appending a `BackendSpec`, not copy-pasting a spawn block.
+#[derive(Clone, Copy)]
+struct BackendSpec {
+  name: &'static str,
+  bin_base_name: &'static str,
+  port: u16,
+  env: EnvBuilder,
+  probe: ProbeBuilder,
+  /// Names of backends this one depends on: they are started before it (it is still started
+  /// if one of them fails to come up), and a restart of any of them also restarts this one.
+  depends_on: &'static [&'static str],
+  startup_timeout: Duration,
+}
+
+/// Computes a start order over `specs` such that every backend comes after everything it
+/// depends on (Kahn's algorithm). Returns an error listing every backend name if the
+/// dependency graph isn't a DAG, instead of starting anything.
+fn topological_order(specs: &[BackendSpec]) -> Result<Vec<usize>, String> {
+  let n = specs.len();
+  let index_of = |name: &str| specs.iter().position(|s| s.name == name);
+
+  let mut in_degree = vec![0usize; n];
+  let mut dependents: Vec<Vec<usize>> = vec![vec![]; n];
+  for (i, spec) in specs.iter().enumerate() {
+    for dep_name in spec.depends_on {
+      let dep_idx = index_of(dep_name)
+        .ok_or_else(|| format!("{} depends on unknown backend {}", spec.name, dep_name))?;
+      dependents[dep_idx].push(i);
+      in_degree[i] += 1;
+    }
+  }
+
+  let mut queue: VecDeque<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+  let mut order = vec![];
+  while let Some(i) = queue.pop_front() {
+    order.push(i);
+    for &next in &dependents[i] {
+      in_degree[next] -= 1;
+      if in_degree[next] == 0 {
+        queue.push_back(next);
+      }
+    }
+  }
+
+  if order.len() != n {
+    return Err(format!(
+      "dependency cycle detected among backends: {:?}",
+      specs.iter().map(|s| s.name).collect::<Vec<_>>()
+    ));
+  }
+  Ok(order)
+}
+
+/// Names of backends that declare `name` in their `depends_on` — i.e. backends that must be
+/// restarted alongside `name` so their injected URL keeps pointing at a live process.
+fn dependents_of<'a>(specs: &'a [BackendSpec], name: &str) -> Vec<&'a str> {
+  specs
+    .iter()
+    .filter(|s| s.depends_on.contains(&name))
+    .map(|s| s.name)
+    .collect()
+}
+
+fn resolved_urls(specs: &[BackendSpec]) -> HashMap<&'static str, String> {
+  specs
+    .iter()
+    .map(|s| (s.name, format!("http://127.0.0.1:{}", s.port)))
+    .collect()
+}
+
+fn http_health_probe(port: u16) -> ReadinessProbe {
+  ReadinessProbe::http(port, "/health", 200, Duration::from_millis(800), Duration::from_millis(200))
+}
+
+fn ai_service_env(app: &AppHandle, port: u16, _resolved: &HashMap<&'static str, String>) -> Vec<(&'static str, String)> {
+  // Provide a stable app-specific data directory so ai-service can persist runtime config
+  // (e.g. model provider, model id, API keys) without relying on env vars.
+  let app_data_dir = app
+    .path()
+    .app_data_dir()
+    .ok()
+    .and_then(|p| {
+      let _ = std::fs::create_dir_all(&p);
+      Some(p.to_string_lossy().to_string())
+    })
+    .unwrap_or_else(|| ".".to_string());
+
+  vec![
+    ("PORT", port.to_string()),
+    ("NODE_ENV", "production".to_string()),
+    ("KARIOS_APP_DATA_DIR", app_data_dir),
+  ]
+}
+
+fn quant_service_env(app: &AppHandle, port: u16, resolved: &HashMap<&'static str, String>) -> Vec<(&'static str, String)> {
+  let database_path = app
+    .path()
+    .app_data_dir()
+    .ok()
+    .and_then(|p| {
+      let _ = std::fs::create_dir_all(&p);
+      Some(p.join("karios.sqlite3").to_string_lossy().to_string())
+    })
+    .unwrap_or_else(|| "karios.sqlite3".to_string());
+
+  vec![
+    ("HOST", "127.0.0.1".to_string()),
+    ("PORT", port.to_string()),
+    (
+      "AI_SERVICE_BASE_URL",
+      resolved
+        .get("karios-ai-service")
+        .cloned()
+        .unwrap_or_else(|| "http://127.0.0.1:4310".to_string()),
+    ),
+    ("PYTHONUNBUFFERED", "1".to_string()),
+    ("DATABASE_PATH", database_path),
+  ]
+}
+
+/// The bundled sidecar topology. `quant-service` depends on `ai-service`, so it starts
+/// after it and has `ai-service`'s resolved URL injected as `AI_SERVICE_BASE_URL`.
+fn default_specs() -> Vec<BackendSpec> {
+  vec![
+    BackendSpec {
+      name: "karios-ai-service",
+      bin_base_name: "karios-ai-service",
+      port: 4310,
+      env: ai_service_env,
+      probe: http_health_probe,
+      depends_on: &[],
+      startup_timeout: Duration::from_secs(10),
+    },
+    BackendSpec {
+      name: "karios-quant-service",
+      bin_base_name: "karios-quant-service",
+      port: 4320,
+      env: quant_service_env,
+      probe: http_health_probe,
+      depends_on: &["karios-ai-service"],
+      startup_timeout: Duration::from_secs(25),
+    },
+  ]
+}
+
+/// Size-based rotation for sidecar log files: once `{name}.log` reaches `max_bytes`, it's
+/// shifted to `{name}.log.1` (and older generations shifted up in turn), dropping anything
+/// past `max_generations` so a chatty sidecar can't grow its logs without bound.
+#[derive(Debug, Clone, Copy)]
+struct LogRotationPolicy {
+  max_bytes: u64,
+  max_generations: u32,
+}
+
+impl Default for LogRotationPolicy {
+  fn default() -> Self {
+    Self {
+      max_bytes: 10 * 1024 * 1024,
+      max_generations: 5,
+    }
+  }
+}
+
+fn rotated_log_path(log_path: &Path, generation: u32) -> PathBuf {
+  let mut name = log_path.as_os_str().to_owned();
+  name.push(format!(".{generation}"));
+  PathBuf::from(name)
+}
+
+/// Rotates `log_path` if it has reached `policy.max_bytes`. Called both on first spawn and
+/// on every supervised restart, since the sidecar keeps appending to whatever handle it was
+/// given and won't notice the file underneath it move.
+fn rotate_log_if_needed(log_path: &Path, policy: &LogRotationPolicy) {
+  let Ok(meta) = std::fs::metadata(log_path) else {
+    return;
+  };
+  if meta.len() < policy.max_bytes {
+    return;
+  }
+
+  let _ = std::fs::remove_file(rotated_log_path(log_path, policy.max_generations));
+  for generation in (1..policy.max_generations).rev() {
+    let from = rotated_log_path(log_path, generation);
+    if from.exists() {
+      let _ = std::fs::rename(&from, rotated_log_path(log_path, generation + 1));
+    }
+  }
+  let _ = std::fs::rename(log_path, rotated_log_path(log_path, 1));
+}
+
+/// Restart policy for a single supervised backend: exponential backoff with a ceiling,
+/// a sliding window used to cap the number of restarts, and a grace period after which
+/// a healthy child resets its attempt counter back to zero.
+#[derive(Debug, Clone, Copy)]
+struct RestartPolicy {
+  max_restarts: u32,
+  window: Duration,
+  base_delay: Duration,
+  max_delay: Duration,
+  healthy_reset_after: Duration,
+}
+
+impl Default for RestartPolicy {
+  fn default() -> Self {
+    Self {
+      max_restarts: 5,
+      window: Duration::from_secs(60),
+      base_delay: Duration::from_millis(500),
+      max_delay: Duration::from_secs(30),
+      healthy_reset_after: Duration::from_secs(120),
+    }
+  }
+}
+
+impl RestartPolicy {
+  fn backoff_for(&self, attempt: u32) -> Duration {
+    let scaled = self.base_delay.saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX));
+    std::cmp::min(scaled, self.max_delay)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendState {
+  Starting,
+  Ready,
+  Restarting,
+  Failed,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct BackendStateChanged {
+  name: &'static str,
+  state: BackendState,
+  port: u16,
+}
+
+/// How a backend is checked for readiness (at spawn time) and liveness (ongoing, by the
+/// supervisor). `Tcp` only confirms something accepted a connection; `Http` confirms the
+/// service itself answered a health check, which catches a process that is listening but
+/// stuck (e.g. still loading models or blocked opening its SQLite DB).
+#[derive(Debug, Clone)]
+struct ReadinessProbe {
+  port: u16,
+  kind: ProbeKind,
+  /// How often to poll while waiting for readiness. Liveness checks run on the supervisor's
+  /// own fixed tick instead.
+  interval: Duration,
+}
+
+#[derive(Debug, Clone)]
+enum ProbeKind {
+  Tcp,
+  Http {
+    path: &'static str,
+    expected_status: u16,
+    request_timeout: Duration,
+  },
+}
+
+impl ReadinessProbe {
+  fn tcp(port: u16, interval: Duration) -> Self {
+    Self { port, kind: ProbeKind::Tcp, interval }
+  }
+
+  fn http(port: u16, path: &'static str, expected_status: u16, request_timeout: Duration, interval: Duration) -> Self {
+    Self {
+      port,
+      kind: ProbeKind::Http { path, expected_status, request_timeout },
+      interval,
+    }
+  }
+
+  /// Performs a single check, returning whether the backend currently looks healthy.
+  fn check(&self) -> bool {
+    match &self.kind {
+      ProbeKind::Tcp => is_port_open(self.port),
+      ProbeKind::Http { path, expected_status, request_timeout } => {
+        let url = format!("http://127.0.0.1:{}{}", self.port, path);
+        reqwest::blocking::Client::builder()
+          .timeout(*request_timeout)
+          .build()
+          .and_then(|client| client.get(&url).send())
+          .map(|resp| resp.status().as_u16() == *expected_status)
+          .unwrap_or(false)
+      }
+    }
+  }
+
+  /// Polls `check` until it succeeds or `startup_timeout` elapses.
+  fn wait_ready(&self, startup_timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < startup_timeout {
+      if self.check() {
+        return true;
+      }
+      std::thread::sleep(self.interval);
+    }
+    false
+  }
+}
 
 #[derive(Debug)]
 struct BackendChild {
   name: &'static str,
+  bin_base_name: &'static str,
   port: u16,
   child: Child,
+  envs: Vec<(&'static str, String)>,
+  probe: ReadinessProbe,
+  startup_timeout: Duration,
+  state: BackendState,
+  policy: RestartPolicy,
+  /// Timestamps of restarts within the current sliding window, oldest first.
+  restart_times: Vec<Instant>,
+  started_at: Instant,
+  /// Unix-epoch-millisecond timestamps of the last `RESTART_HISTORY_LEN` restarts, exposed
+  /// to the frontend via `backend_status` (unlike `restart_times`, this survives across the
+  /// monotonic-clock-only bookkeeping used for the backoff window).
+  restart_log: Vec<u64>,
+}
+
+/// How many past restart timestamps `BackendChild::restart_log` keeps for the status API.
+const RESTART_HISTORY_LEN: usize = 10;
+
+/// Per-backend status reported to the frontend by the `backend_status` command. State
+/// changes are pushed separately as `karios://backend-state-changed` events, which carry
+/// only the name, state and port.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendStatus {
+  pub name: &'static str,
+  pub port: u16,
+  pub pid: u32,
+  pub state: BackendState,
+  pub restart_log: Vec<u64>,
 }
 
-#[derive(Default)]
 pub struct BackendManager {
   children: Mutex<Vec<BackendChild>>,
+  shutting_down: AtomicBool,
+  /// How long `stop_all` waits after SIGTERM/taskkill before escalating to a hard kill.
+  shutdown_grace: Duration,
+  log_rotation: LogRotationPolicy,
+  specs: Vec<BackendSpec>,
+}
+
+impl Default for BackendManager {
+  fn default() -> Self {
+    Self {
+      children: Mutex::new(vec![]),
+      shutting_down: AtomicBool::new(false),
+      shutdown_grace: Duration::from_secs(8),
+      log_rotation: LogRotationPolicy::default(),
+      specs: default_specs(),
+    }
+  }
 }
 
 fn is_port_open(port: u16) -> bool {
@@ -26,17 +383,6 @@ fn is_port_open(port: u16) -> bool {
   .is_ok()
 }
 
-fn wait_port(port: u16, timeout: Duration) -> bool {
-  let start = Instant::now();
-  while start.elapsed() < timeout {
-    if is_port_open(port) {
-      return true;
-    }
-    std::thread::sleep(Duration::from_millis(120));
-  }
-  false
-}
-
 fn exe_suffix() -> &'static str {
   if cfg!(windows) { ".exe" } else { "" }
 }
@@ -92,14 +438,16 @@ fn find_external_bin(app: &AppHandle, base_name: &str) -> Option<PathBuf> {
 fn spawn_backend(
   app: &AppHandle,
   name: &'static str,
-  port: u16,
-  timeout: Duration,
-  envs: &[(&str, String)],
+  bin_base_name: &str,
+  probe: &ReadinessProbe,
+  startup_timeout: Duration,
+  envs: &[(&'static str, String)],
+  log_rotation: &LogRotationPolicy,
 ) -> Result<Child, String> {
-  let bin = find_external_bin(app, name).ok_or_else(|| {
+  let bin = find_external_bin(app, bin_base_name).ok_or_else(|| {
     format!(
       "Sidecar binary not found: {} (searched in {:?})",
-      name,
+      bin_base_name,
       candidate_dirs(app)
     )
   })?;
@@ -112,6 +460,7 @@ fn spawn_backend(
   let _ = create_dir_all(&log_dir);
 
   let log_path = log_dir.join(format!("{name}.log"));
+  rotate_log_if_needed(&log_path, log_rotation);
   let log_file = OpenOptions::new()
     .create(true)
     .append(true)
@@ -139,15 +488,98 @@ fn spawn_backend(
     .spawn()
     .map_err(|e| format!("Failed to spawn {name} ({:?}): {e}", bin))?;
 
-  if !wait_port(port, timeout) {
+  if !probe.wait_ready(startup_timeout) {
     return Err(format!(
-      "{name} did not become ready on port {port} within timeout"
+      "{name} did not become ready on port {} within timeout",
+      probe.port
     ));
   }
 
   Ok(child)
 }
 
+/// Asks a child to exit on its own: SIGTERM on Unix, a non-forceful `taskkill` on Windows
+/// (our sidecars are console processes with no window to post `WM_CLOSE` to). Callers are
+/// expected to poll `try_wait()` afterwards and escalate to `Child::kill` if it lingers.
+fn terminate_gracefully(child: &mut Child) -> Result<(), String> {
+  #[cfg(unix)]
+  {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+    kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM).map_err(|e| format!("SIGTERM failed: {e}"))
+  }
+  #[cfg(windows)]
+  {
+    Command::new("taskkill")
+      .args(["/PID", &child.id().to_string()])
+      .output()
+      .map(|_| ())
+      .map_err(|e| format!("taskkill failed: {e}"))
+  }
+}
+
+/// Stops `child` (named `name`, for logging) before it's replaced or the manager shuts down:
+/// `terminate_gracefully`, then up to `grace` polling for it to exit on its own, then a hard
+/// `kill()` if it's still alive. Shared by `stop_all` and `restart_backend`'s kill-before-
+/// respawn path — a replacement process must never be started while the old one still holds
+/// the port.
+fn stop_child(child: &mut Child, name: &str, grace: Duration) {
+  if let Err(err) = terminate_gracefully(child) {
+    eprintln!("[karios] graceful stop failed for {name}: {err}; killing");
+    let _ = child.kill();
+    return;
+  }
+
+  let start = Instant::now();
+  let mut exited = false;
+  while start.elapsed() < grace {
+    match child.try_wait() {
+      Ok(Some(_)) => {
+        exited = true;
+        break;
+      }
+      Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+      Err(_) => break,
+    }
+  }
+
+  if !exited {
+    eprintln!("[karios] sidecar {name} did not exit within {grace:?}; killing");
+    let _ = child.kill();
+  }
+}
+
+/// Orders children for shutdown: backends that other backends depend on are stopped last,
+/// so e.g. `ai-service` stays up until `quant-service` is already gone. This is the reverse
+/// of the dependency-respecting start order, i.e. `topological_order` run backwards — not
+/// merely "has any dependent", which collapses a 3+ level chain (A <- B <- C would stop as
+/// [C, A, B], killing A while B still depends on it).
+///
+/// `children` may be a subset of `specs` (some backends can fail to start), so spec indices
+/// are mapped to child indices by name and any spec without a matching child is skipped.
+fn shutdown_order(specs: &[BackendSpec], children: &[BackendChild]) -> Vec<usize> {
+  let start_order = match topological_order(specs) {
+    Ok(order) => order,
+    Err(_) => return (0..children.len()).collect(),
+  };
+
+  start_order
+    .into_iter()
+    .rev()
+    .filter_map(|spec_idx| {
+      let name = specs[spec_idx].name;
+      children.iter().position(|c| c.name == name)
+    })
+    .collect()
+}
+
+fn emit_state(app: &AppHandle, name: &'static str, port: u16, state: BackendState) {
+  let _ = app.emit(
+    "karios://backend-state-changed",
+    BackendStateChanged { name, state, port },
+  );
+}
+
 static START_ONCE: OnceLock<()> = OnceLock::new();
 
 impl BackendManager {
@@ -164,103 +596,443 @@ impl BackendManager {
       return;
     }
 
-    // Start ai-service first (quant-service depends on it).
-    let ai_port: u16 = 4310;
-    let quant_port: u16 = 4320;
+    let order = match topological_order(&self.specs) {
+      Ok(order) => order,
+      Err(err) => {
+        eprintln!("[karios] refusing to start backends: {err}");
+        return;
+      }
+    };
+
+    // Every dependency's URL is resolved from its spec up front (ports are static), so a
+    // dependent's `env` builder can read e.g. `resolved["karios-ai-service"]` instead of a
+    // literal that has to be kept in sync by hand.
+    let resolved = resolved_urls(&self.specs);
 
     let mut spawned: Vec<BackendChild> = vec![];
+    for idx in order {
+      let spec = self.specs[idx];
+      let envs = (spec.env)(app, spec.port, &resolved);
+      let probe = (spec.probe)(spec.port);
 
-    // Provide a stable app-specific data directory so ai-service can persist runtime config
-    // (e.g. model provider, model id, API keys) without relying on env vars.
-    let app_data_dir = app
-      .path()
-      .app_data_dir()
-      .ok()
-      .and_then(|p| {
-        let _ = std::fs::create_dir_all(&p);
-        Some(p.to_string_lossy().to_string())
-      })
-      .unwrap_or_else(|| ".".to_string());
-
-    let ai = spawn_backend(
-      app,
-      "karios-ai-service",
-      ai_port,
-      Duration::from_secs(10),
-      &[
-        ("PORT", ai_port.to_string()),
-        ("NODE_ENV", "production".to_string()),
-        ("KARIOS_APP_DATA_DIR", app_data_dir.clone()),
-      ],
-    );
-
-    match ai {
-      Ok(child) => {
-        eprintln!("[karios] started sidecar: karios-ai-service on 127.0.0.1:{ai_port}");
-        spawned.push(BackendChild {
-          name: "karios-ai-service",
-          port: ai_port,
-          child,
-        });
-      }
-      Err(err) => {
-        eprintln!("[karios] failed to start ai-service sidecar: {err}");
-        // If AI is unavailable, quant-service will still run but strategy features will fail.
+      emit_state(app, spec.name, spec.port, BackendState::Starting);
+
+      match spawn_backend(
+        app,
+        spec.name,
+        spec.bin_base_name,
+        &probe,
+        spec.startup_timeout,
+        &envs,
+        &self.log_rotation,
+      ) {
+        Ok(child) => {
+          eprintln!("[karios] started sidecar: {} on 127.0.0.1:{}", spec.name, spec.port);
+          emit_state(app, spec.name, spec.port, BackendState::Ready);
+          spawned.push(BackendChild {
+            name: spec.name,
+            bin_base_name: spec.bin_base_name,
+            port: spec.port,
+            child,
+            envs,
+            probe,
+            startup_timeout: spec.startup_timeout,
+            state: BackendState::Ready,
+            policy: RestartPolicy::default(),
+            restart_times: vec![],
+            started_at: Instant::now(),
+            restart_log: vec![],
+          });
+        }
+        Err(err) => {
+          eprintln!("[karios] failed to start {} sidecar: {err}", spec.name);
+          // Dependents are started anyway; they'll simply fail their own readiness probe
+          // (or run degraded) if they truly need this backend.
+        }
       }
     }
 
-    let quant_envs = [
-      ("HOST", "127.0.0.1".to_string()),
-      ("PORT", quant_port.to_string()),
-      ("AI_SERVICE_BASE_URL", format!("http://127.0.0.1:{ai_port}")),
-      ("PYTHONUNBUFFERED", "1".to_string()),
+    *self.children.lock().expect("backend children lock poisoned") = spawned;
+
+    self.start_supervisor(app.clone());
+  }
+
+  /// Spawns a background thread that watches every supervised child and restarts it
+  /// (with exponential backoff, capped by `RestartPolicy`) if it exits unexpectedly, or if
+  /// its `ReadinessProbe` stops reporting healthy (a wedged-but-listening process). A
+  /// restart of a backend also restarts its dependents (per `BackendSpec::depends_on`), so
+  /// their environment keeps pointing at a live process.
+  fn start_supervisor(&self, app: AppHandle) {
+    std::thread::spawn(move || loop {
+      std::thread::sleep(Duration::from_secs(2));
+
+      let mgr = app.state::<BackendManager>();
+      if mgr.shutting_down.load(Ordering::SeqCst) {
+        return;
+      }
+
+      // `try_wait` is a cheap, non-blocking syscall, so it's fine to do under the lock. The
+      // HTTP liveness probe is a real (if short) blocking network call, so it must run with
+      // the lock released — otherwise `backend_status`/`restart_backend`/`stop_all` would
+      // stall behind it on every tick. Collect candidates first, probe after dropping the lock.
+      let mut to_restart = vec![];
+      let mut to_probe: Vec<(&'static str, ReadinessProbe)> = vec![];
+      {
+        let mut children = mgr.children.lock().expect("backend children lock poisoned");
+        for child in children.iter_mut() {
+          if child.state == BackendState::Failed {
+            continue;
+          }
+          match child.child.try_wait() {
+            Ok(Some(status)) => {
+              eprintln!("[karios] sidecar {} exited unexpectedly: {status}", child.name);
+              to_restart.push(child.name);
+            }
+            Ok(None) if child.state == BackendState::Ready => {
+              to_probe.push((child.name, child.probe.clone()));
+              // Still running; reset the attempt window once it's stayed up long enough.
+              if child.started_at.elapsed() >= child.policy.healthy_reset_after {
+                child.restart_times.clear();
+              }
+            }
+            Ok(None) => {}
+            Err(err) => {
+              eprintln!("[karios] failed to poll sidecar {}: {err}", child.name);
+            }
+          }
+        }
+      }
+
+      for (name, probe) in to_probe {
+        if !probe.check() {
+          eprintln!("[karios] sidecar {name} is listening but failing its liveness probe; recycling");
+          to_restart.push(name);
+        }
+      }
+
+      // A crash of both a backend and its dependent in the same tick would otherwise queue
+      // the dependent twice (once directly, once via `dependents_of`); restart each name at
+      // most once per tick.
+      let mut restarted = HashSet::new();
+      for name in to_restart {
+        if restarted.insert(name) {
+          mgr.restart_backend(&app, name);
+        }
+        for dependent in dependents_of(&mgr.specs, name) {
+          if restarted.insert(dependent) {
+            eprintln!("[karios] restarting dependent {dependent} after {name} restart");
+            mgr.restart_backend(&app, dependent);
+          }
+        }
+      }
+    });
+  }
+
+  /// Restarts a single supervised backend in place, applying the restart policy's
+  /// exponential backoff and sliding-window restart cap. Marks the backend permanently
+  /// `Failed` (and stops retrying it) once it exceeds `max_restarts` within `window`.
+  fn restart_backend(&self, app: &AppHandle, name: &'static str) {
+    let (bin_base_name, probe, startup_timeout, envs, policy, attempt) = {
+      let mut children = self.children.lock().expect("backend children lock poisoned");
+      let Some(child) = children.iter_mut().find(|c| c.name == name) else {
+        return;
+      };
+
+      // A restart is already in flight (triggered by the supervisor loop or a concurrent
+      // `restart_backend` call) — bail out instead of spawning a second process for the
+      // same backend/port.
+      if child.state == BackendState::Restarting || child.state == BackendState::Starting {
+        return;
+      }
+
+      let now = Instant::now();
+      child.restart_times.retain(|t| now.duration_since(*t) <= child.policy.window);
+      child.restart_times.push(now);
+
+      let epoch_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+      child.restart_log.push(epoch_ms);
+      if child.restart_log.len() > RESTART_HISTORY_LEN {
+        child.restart_log.remove(0);
+      }
+
+      if child.restart_times.len() as u32 > child.policy.max_restarts {
+        eprintln!(
+          "[karios] sidecar {name} exceeded {} restarts within {:?}; marking permanently failed",
+          child.policy.max_restarts, child.policy.window
+        );
+        child.state = BackendState::Failed;
+        emit_state(app, name, child.port, BackendState::Failed);
+        return;
+      }
+
+      child.state = BackendState::Restarting;
+      emit_state(app, name, child.port, BackendState::Restarting);
       (
-        "DATABASE_PATH",
-        app
-          .path()
-          .app_data_dir()
-          .ok()
-          .and_then(|p| {
-            let _ = std::fs::create_dir_all(&p);
-            Some(p.join("karios.sqlite3").to_string_lossy().to_string())
-          })
-          .unwrap_or_else(|| "karios.sqlite3".to_string()),
-      ),
-    ];
-
-    let quant = spawn_backend(
-      app,
-      "karios-quant-service",
-      quant_port,
-      Duration::from_secs(25),
-      &quant_envs,
-    );
-    match quant {
-      Ok(child) => {
-        eprintln!("[karios] started sidecar: karios-quant-service on 127.0.0.1:{quant_port}");
-        spawned.push(BackendChild {
-          name: "karios-quant-service",
-          port: quant_port,
-          child,
-        });
+        child.bin_base_name,
+        child.probe.clone(),
+        child.startup_timeout,
+        child.envs.clone(),
+        child.policy,
+        child.restart_times.len() as u32 - 1,
+      )
+    };
+
+    let delay = policy.backoff_for(attempt);
+    std::thread::sleep(delay);
+
+    let port = probe.port;
+    {
+      let mut children = self.children.lock().expect("backend children lock poisoned");
+      if let Some(child) = children.iter_mut().find(|c| c.name == name) {
+        child.state = BackendState::Starting;
+        // The backend being restarted isn't necessarily dead — a crashed dependency or a
+        // failed liveness probe both restart a process that may still be holding the port.
+        // Stop it the same way `stop_all` does before spawning its replacement, or the new
+        // process will contend for the port with an unsupervised orphan of the old one.
+        if matches!(child.child.try_wait(), Ok(None)) {
+          stop_child(&mut child.child, name, self.shutdown_grace);
+        }
+      }
+    }
+    emit_state(app, name, port, BackendState::Starting);
+
+    match spawn_backend(app, name, bin_base_name, &probe, startup_timeout, &envs, &self.log_rotation) {
+      Ok(new_child) => {
+        eprintln!("[karios] restarted sidecar: {name} on 127.0.0.1:{port} (attempt {attempt})");
+        let mut children = self.children.lock().expect("backend children lock poisoned");
+        if let Some(child) = children.iter_mut().find(|c| c.name == name) {
+          child.child = new_child;
+          child.state = BackendState::Ready;
+          child.started_at = Instant::now();
+        }
+        emit_state(app, name, port, BackendState::Ready);
       }
       Err(err) => {
-        eprintln!("[karios] failed to start quant-service sidecar: {err}");
+        eprintln!("[karios] failed to restart sidecar {name}: {err}");
+        let mut children = self.children.lock().expect("backend children lock poisoned");
+        if let Some(child) = children.iter_mut().find(|c| c.name == name) {
+          child.state = BackendState::Failed;
+        }
+        emit_state(app, name, port, BackendState::Failed);
       }
     }
+  }
 
-    *self.children.lock().expect("backend children lock poisoned") = spawned;
+  /// Marks the manager as shutting down (stopping the supervisor from restarting anything)
+  /// and reports whether this call was the one that transitioned it, so callers only kick
+  /// off the shutdown sequence once.
+  pub fn begin_shutdown(&self) -> bool {
+    self
+      .shutting_down
+      .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+      .is_ok()
   }
 
+  /// Stops every backend gracefully: dependents are asked to exit before the backends they
+  /// depend on (see `shutdown_order`), each gets SIGTERM/taskkill and up to `shutdown_grace`
+  /// to exit on its own, and only a sidecar still alive after that is hard-killed.
   pub fn stop_all(&self) {
+    self.shutting_down.store(true, Ordering::SeqCst);
     let mut children = self.children.lock().expect("backend children lock poisoned");
-    for c in children.iter_mut() {
+    for idx in shutdown_order(&self.specs, &children) {
+      let c = &mut children[idx];
       eprintln!("[karios] stopping sidecar: {} on 127.0.0.1:{}", c.name, c.port);
-      // Best-effort: ignore failures
-      let _ = c.child.kill();
+      stop_child(&mut c.child, c.name, self.shutdown_grace);
     }
     children.clear();
   }
+
+  /// Snapshot of every supervised backend's name, port, PID, state, and restart history —
+  /// backing the `backend_status` Tauri command.
+  pub fn status_snapshot(&self) -> Vec<BackendStatus> {
+    let children = self.children.lock().expect("backend children lock poisoned");
+    children
+      .iter()
+      .map(|c| BackendStatus {
+        name: c.name,
+        port: c.port,
+        pid: c.child.id(),
+        state: c.state,
+        restart_log: c.restart_log.clone(),
+      })
+      .collect()
+  }
+
+  /// Forces a supervised restart of the named backend, bypassing the usual "it crashed"
+  /// trigger. Runs on a background thread (restarts respect the usual backoff delay) so the
+  /// calling Tauri command returns immediately; backs the `restart_backend` command.
+  pub fn restart_named(&self, app: &AppHandle, name: &str) -> Result<(), String> {
+    let static_name = {
+      let children = self.children.lock().expect("backend children lock poisoned");
+      children.iter().find(|c| c.name == name).map(|c| c.name)
+    };
+    let Some(static_name) = static_name else {
+      return Err(format!("unknown backend: {name}"));
+    };
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+      app.state::<BackendManager>().restart_backend(&app, static_name);
+    });
+    Ok(())
+  }
 }
 
+/// Reads the last `lines` lines of `{name}.log` under `app_data_dir/logs`, i.e. the backend's
+/// currently-active (post-rotation) log file. Backs the `tail_backend_log` Tauri command.
+pub(crate) fn tail_log(app: &AppHandle, name: &str, lines: usize) -> Result<Vec<String>, String> {
+  let log_path = app
+    .path()
+    .app_data_dir()
+    .map(|p| p.join("logs").join(format!("{name}.log")))
+    .map_err(|e| format!("Failed to resolve app_data_dir for logs: {e}"))?;
+
+  let bytes = std::fs::read(&log_path).map_err(|e| format!("Failed to read log file {:?}: {e}", log_path))?;
+  // Sidecars aren't guaranteed to only ever write valid UTF-8 (e.g. a panic message echoing
+  // raw bytes); lossily decode rather than failing the whole tail.
+  let content = String::from_utf8_lossy(&bytes);
+
+  let all_lines: Vec<&str> = content.lines().collect();
+  let start = all_lines.len().saturating_sub(lines);
+  Ok(all_lines[start..].iter().map(|s| s.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn spec(name: &'static str, depends_on: &'static [&'static str]) -> BackendSpec {
+    BackendSpec {
+      name,
+      bin_base_name: name,
+      port: 0,
+      env: |_, _, _| vec![],
+      probe: http_health_probe,
+      depends_on,
+      startup_timeout: Duration::from_secs(1),
+    }
+  }
+
+  fn spawn_noop_child() -> Child {
+    #[cfg(windows)]
+    {
+      Command::new("cmd").args(["/C", "exit 0"]).spawn().expect("failed to spawn no-op test process")
+    }
+    #[cfg(not(windows))]
+    {
+      Command::new("true").spawn().expect("failed to spawn no-op test process")
+    }
+  }
+
+  /// A child that stays alive long enough to assert against before a graceful-stop signal
+  /// cuts it short.
+  fn spawn_sleeping_child() -> Child {
+    #[cfg(windows)]
+    {
+      Command::new("cmd").args(["/C", "timeout /T 5"]).spawn().expect("failed to spawn test process")
+    }
+    #[cfg(not(windows))]
+    {
+      Command::new("sleep").arg("5").spawn().expect("failed to spawn test process")
+    }
+  }
+
+  #[cfg(unix)]
+  fn is_process_alive(pid: u32) -> bool {
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+    kill(Pid::from_raw(pid as i32), None).is_ok()
+  }
+
+  #[cfg(windows)]
+  fn is_process_alive(pid: u32) -> bool {
+    let output = Command::new("tasklist").args(["/FI", &format!("PID eq {pid}")]).output();
+    match output {
+      Ok(out) => String::from_utf8_lossy(&out.stdout).contains(&pid.to_string()),
+      Err(_) => false,
+    }
+  }
+
+  fn child_for(spec: &BackendSpec) -> BackendChild {
+    BackendChild {
+      name: spec.name,
+      bin_base_name: spec.bin_base_name,
+      port: spec.port,
+      child: spawn_noop_child(),
+      envs: vec![],
+      probe: (spec.probe)(spec.port),
+      startup_timeout: spec.startup_timeout,
+      state: BackendState::Ready,
+      policy: RestartPolicy::default(),
+      restart_times: vec![],
+      started_at: Instant::now(),
+      restart_log: vec![],
+    }
+  }
+
+  #[test]
+  fn topological_order_respects_dependencies() {
+    let specs = vec![spec("a", &[]), spec("b", &["a"]), spec("c", &["b"])];
+    let order = topological_order(&specs).expect("no cycle");
+    let pos = |name: &str| order.iter().position(|&i| specs[i].name == name).unwrap();
+    assert!(pos("a") < pos("b"));
+    assert!(pos("b") < pos("c"));
+  }
 
+  #[test]
+  fn topological_order_detects_cycle() {
+    let specs = vec![spec("a", &["b"]), spec("b", &["a"])];
+    assert!(topological_order(&specs).is_err());
+  }
+
+  #[test]
+  fn shutdown_order_reverses_a_three_node_chain() {
+    // c depends on b depends on a. The old "has any dependent" partition only sorted
+    // has-dependents-first vs not, which put both a and b in the "has a dependent" bucket
+    // and left their relative order unspecified — letting a (still depended on by b) stop
+    // before b. A real reverse topological order must stop c, then b, then a.
+    let specs = vec![spec("a", &[]), spec("b", &["a"]), spec("c", &["b"])];
+    let children = vec![child_for(&specs[0]), child_for(&specs[1]), child_for(&specs[2])];
+    let order = shutdown_order(&specs, &children);
+    let pos = |name: &str| order.iter().position(|&i| children[i].name == name).unwrap();
+    assert!(pos("c") < pos("b"));
+    assert!(pos("b") < pos("a"));
+  }
+
+  #[test]
+  fn shutdown_order_skips_specs_with_no_matching_child() {
+    // "b" failed to start, so there's no BackendChild for it; shutdown_order must still
+    // order the children that do exist without panicking on the missing one.
+    let specs = vec![spec("a", &[]), spec("b", &["a"])];
+    let children = vec![child_for(&specs[0])];
+    assert_eq!(shutdown_order(&specs, &children), vec![0]);
+  }
+
+  #[test]
+  fn backoff_for_grows_exponentially_then_caps() {
+    let policy = RestartPolicy::default();
+    assert_eq!(policy.backoff_for(0), policy.base_delay);
+    assert_eq!(policy.backoff_for(1), policy.base_delay * 2);
+    assert_eq!(policy.backoff_for(2), policy.base_delay * 4);
+    assert_eq!(policy.backoff_for(20), policy.max_delay);
+  }
+
+  #[test]
+  fn stop_child_kills_a_still_running_process() {
+    // Regression test for `restart_backend`'s "recycle a wedged-but-listening process" and
+    // "restart a dependent that never crashed" paths: both call `stop_child` (the same
+    // helper `stop_all` uses) against a child that is still running, before replacing it.
+    // The old process must actually be dead afterwards, not leaked as an unsupervised
+    // orphan still holding the port — `Child`'s `Drop` alone does not kill it.
+    let mut child = spawn_sleeping_child();
+    let pid = child.id();
+    assert!(is_process_alive(pid), "test process should start out alive");
+
+    stop_child(&mut child, "karios-test-backend", Duration::from_secs(2));
+
+    assert!(!is_process_alive(pid), "stop_child should have killed the still-running process");
+  }
+}
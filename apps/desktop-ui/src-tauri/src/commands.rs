@@ -0,0 +1,23 @@
+use tauri::{AppHandle, State};
+
+use crate::backends::{self, BackendManager, BackendStatus};
+
+/// Per-backend name/port/PID/state and recent restart history, for a live backend-health
+/// panel in the renderer.
+#[tauri::command]
+pub fn backend_status(manager: State<'_, BackendManager>) -> Vec<BackendStatus> {
+  manager.status_snapshot()
+}
+
+/// Forces a supervised restart of `name`. Returns once the restart has been scheduled, not
+/// once it has completed — watch `karios://backend-state-changed` for the outcome.
+#[tauri::command]
+pub fn restart_backend(app: AppHandle, manager: State<'_, BackendManager>, name: String) -> Result<(), String> {
+  manager.restart_named(&app, &name)
+}
+
+/// Returns the last `lines` lines of `name`'s current log file.
+#[tauri::command]
+pub fn tail_backend_log(app: AppHandle, name: String, lines: usize) -> Result<Vec<String>, String> {
+  backends::tail_log(&app, &name, lines)
+}
@@ -1,4 +1,5 @@
 mod backends;
+mod commands;
 
 use tauri::Manager;
 
@@ -8,6 +9,11 @@ use backends::BackendManager;
 pub fn run() {
   tauri::Builder::default()
     .manage(BackendManager::default())
+    .invoke_handler(tauri::generate_handler![
+      commands::backend_status,
+      commands::restart_backend,
+      commands::tail_backend_log,
+    ])
     .setup(|app| {
       if cfg!(debug_assertions) {
         app.handle().plugin(
@@ -25,10 +31,19 @@ pub fn run() {
       Ok(())
     })
     .on_window_event(|window, event| {
-      // Ensure sidecars are terminated when the main window is closed.
-      if matches!(event, tauri::WindowEvent::CloseRequested { .. }) {
+      // Give sidecars a chance to flush (SQLite WAL, sockets) before the window closes.
+      // The first CloseRequested is suppressed while we shut them down gracefully in the
+      // background; the subsequent programmatic `window.close()` is let through.
+      if let tauri::WindowEvent::CloseRequested { api, .. } = event {
         let mgr = window.state::<BackendManager>();
-        mgr.stop_all();
+        if mgr.begin_shutdown() {
+          api.prevent_close();
+          let window = window.clone();
+          std::thread::spawn(move || {
+            window.state::<BackendManager>().stop_all();
+            let _ = window.close();
+          });
+        }
       }
     })
     .run(tauri::generate_context!())